@@ -0,0 +1,239 @@
+use std::path::PathBuf;
+use std::time::Duration;
+
+use serde::Deserialize;
+
+/// Bind address, sidecar command and startup timing, loaded once at launch.
+///
+/// Resolution order (later wins): built-in defaults, `config.toml` in the
+/// app config dir, then `AGENTOS_*` environment variables. This lets the
+/// same binary point at a custom port, a prebuilt binary, or an
+/// already-running remote server without a rebuild.
+#[derive(Debug, Clone)]
+pub struct ServerConfig {
+    pub host: String,
+    pub port: u16,
+    /// Explicit sidecar command, e.g. `"node"`. `None` falls back to the
+    /// existing dist/server.js-or-tsx auto-detection.
+    pub command: Option<String>,
+    pub args: Vec<String>,
+    /// Explicit working directory for the sidecar. `None` falls back to the
+    /// project root inferred from the current directory.
+    pub working_dir: Option<PathBuf>,
+    pub startup_timeout: Duration,
+    /// Grace period given to the sidecar to exit after a SIGTERM (or
+    /// platform equivalent) before we escalate to a hard kill.
+    pub shutdown_grace: Duration,
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        Self {
+            host: "127.0.0.1".to_string(),
+            port: 3011,
+            command: None,
+            args: Vec::new(),
+            working_dir: None,
+            startup_timeout: Duration::from_secs(30),
+            shutdown_grace: Duration::from_secs(5),
+        }
+    }
+}
+
+impl ServerConfig {
+    /// Number of 500ms polling attempts implied by `startup_timeout`, kept
+    /// for the readiness loop which still counts in fixed-size steps.
+    pub fn max_attempts(&self) -> u32 {
+        (self.startup_timeout.as_millis() / 500).max(1) as u32
+    }
+}
+
+/// Shape of `config.toml`. Every field is optional so the file only needs to
+/// set what it's overriding; anything absent falls back to
+/// `ServerConfig::default()`.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ConfigFile {
+    host: Option<String>,
+    port: Option<u16>,
+    command: Option<String>,
+    args: Option<Vec<String>>,
+    working_dir: Option<PathBuf>,
+    startup_timeout_secs: Option<u64>,
+    shutdown_grace_secs: Option<u64>,
+}
+
+/// Loads the config, reading `config_dir/config.toml` when present and
+/// applying `AGENTOS_*` env var overrides on top.
+pub fn load(config_dir: Option<PathBuf>) -> ServerConfig {
+    let mut config = ServerConfig::default();
+
+    if let Some(dir) = config_dir {
+        let path = dir.join("config.toml");
+        if let Ok(contents) = std::fs::read_to_string(&path) {
+            match toml::from_str::<ConfigFile>(&contents) {
+                Ok(file) => apply_file(&mut config, file),
+                Err(err) => eprintln!("Warning: ignoring invalid config.toml: {err}"),
+            }
+        }
+    }
+
+    apply_env(&mut config);
+    config
+}
+
+fn apply_file(config: &mut ServerConfig, file: ConfigFile) {
+    if let Some(host) = file.host {
+        config.host = host;
+    }
+    if let Some(port) = file.port {
+        config.port = port;
+    }
+    if let Some(command) = file.command {
+        config.command = Some(command);
+    }
+    if let Some(args) = file.args {
+        config.args = args;
+    }
+    if let Some(working_dir) = file.working_dir {
+        config.working_dir = Some(working_dir);
+    }
+    if let Some(secs) = file.startup_timeout_secs {
+        config.startup_timeout = Duration::from_secs(secs);
+    }
+    if let Some(secs) = file.shutdown_grace_secs {
+        config.shutdown_grace = Duration::from_secs(secs);
+    }
+}
+
+/// Splits a shell-style argument list, keeping single- or double-quoted
+/// segments (which may contain spaces) together as one token instead of
+/// naively splitting on whitespace. Only used for `AGENTOS_SERVER_ARGS`,
+/// which (unlike `config.toml`) is a single env var string and so has no
+/// native array syntax to rely on.
+fn tokenize_args(value: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut quote: Option<char> = None;
+    let mut in_token = false;
+
+    for ch in value.chars() {
+        match quote {
+            Some(q) if ch == q => quote = None,
+            Some(_) => current.push(ch),
+            None if ch == '"' || ch == '\'' => {
+                quote = Some(ch);
+                in_token = true;
+            }
+            None if ch.is_whitespace() => {
+                if in_token {
+                    tokens.push(std::mem::take(&mut current));
+                    in_token = false;
+                }
+            }
+            None => {
+                current.push(ch);
+                in_token = true;
+            }
+        }
+    }
+    if in_token {
+        tokens.push(current);
+    }
+
+    tokens
+}
+
+fn apply_env(config: &mut ServerConfig) {
+    if let Ok(host) = std::env::var("AGENTOS_HOST") {
+        config.host = host;
+    }
+    if let Ok(port) = std::env::var("AGENTOS_PORT") {
+        if let Ok(port) = port.parse() {
+            config.port = port;
+        }
+    }
+    if let Ok(command) = std::env::var("AGENTOS_SERVER_COMMAND") {
+        config.command = Some(command);
+    }
+    if let Ok(args) = std::env::var("AGENTOS_SERVER_ARGS") {
+        config.args = tokenize_args(&args);
+    }
+    if let Ok(dir) = std::env::var("AGENTOS_WORKING_DIR") {
+        config.working_dir = Some(PathBuf::from(dir));
+    }
+    if let Ok(secs) = std::env::var("AGENTOS_STARTUP_TIMEOUT_SECS") {
+        if let Ok(secs) = secs.parse() {
+            config.startup_timeout = Duration::from_secs(secs);
+        }
+    }
+    if let Ok(secs) = std::env::var("AGENTOS_SHUTDOWN_GRACE_SECS") {
+        if let Ok(secs) = secs.parse() {
+            config.shutdown_grace = Duration::from_secs(secs);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tokenize_args_splits_on_whitespace() {
+        assert_eq!(tokenize_args("--foo --bar"), vec!["--foo", "--bar"]);
+    }
+
+    #[test]
+    fn tokenize_args_keeps_quoted_segment_together() {
+        assert_eq!(
+            tokenize_args(r#"--foo "--bar baz""#),
+            vec!["--foo", "--bar baz"]
+        );
+    }
+
+    #[test]
+    fn tokenize_args_supports_single_quotes() {
+        assert_eq!(tokenize_args("'a b' c"), vec!["a b", "c"]);
+    }
+
+    #[test]
+    fn tokenize_args_ignores_extra_whitespace() {
+        assert_eq!(tokenize_args("  --foo   --bar  "), vec!["--foo", "--bar"]);
+    }
+
+    #[test]
+    fn apply_file_parses_known_keys() {
+        let mut config = ServerConfig::default();
+        let file: ConfigFile = toml::from_str(
+            "host = \"0.0.0.0\"\nport = 4000\ncommand = \"node\"\nargs = [\"--foo\", \"--bar baz\"]\nstartup_timeout_secs = 10\nshutdown_grace_secs = 2\n",
+        )
+        .unwrap();
+        apply_file(&mut config, file);
+
+        assert_eq!(config.host, "0.0.0.0");
+        assert_eq!(config.port, 4000);
+        assert_eq!(config.command, Some("node".to_string()));
+        assert_eq!(config.args, vec!["--foo", "--bar baz"]);
+        assert_eq!(config.startup_timeout, Duration::from_secs(10));
+        assert_eq!(config.shutdown_grace, Duration::from_secs(2));
+    }
+
+    #[test]
+    fn apply_file_leaves_defaults_for_absent_keys() {
+        let mut config = ServerConfig::default();
+        let before = config.port;
+        let file: ConfigFile = toml::from_str("").unwrap();
+        apply_file(&mut config, file);
+        assert_eq!(config.port, before);
+    }
+
+    #[test]
+    fn apply_file_rejects_unknown_keys() {
+        assert!(toml::from_str::<ConfigFile>("nonsense = true\n").is_err());
+    }
+
+    #[test]
+    fn apply_file_rejects_wrong_value_type() {
+        assert!(toml::from_str::<ConfigFile>("port = \"not-a-number\"\n").is_err());
+    }
+}
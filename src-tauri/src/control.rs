@@ -0,0 +1,122 @@
+use std::io::{Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::OnceLock;
+use std::thread;
+
+use tauri::{AppHandle, Emitter, Manager};
+
+/// Set once in `setup` so the loopback HTTP listener (which runs outside
+/// any Tauri command context) can still resolve the main window.
+static APP_HANDLE: OnceLock<AppHandle> = OnceLock::new();
+
+/// Generates a per-launch token from the OS CSPRNG (`getrandom`), so the
+/// loopback control channel's bearer credential isn't guessable.
+pub fn generate_token() -> String {
+    let mut bytes = [0u8; 32];
+    getrandom::getrandom(&mut bytes).expect("failed to read OS randomness for control token");
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+/// Constant-time comparison so a timing difference in how many leading bytes
+/// match can't be used to guess the control token one byte at a time.
+fn tokens_match(a: &str, b: &str) -> bool {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// Binds a loopback-only HTTP endpoint the Node server can call (with
+/// `X-AgentOS-Token: <token>`) to drive window state: `/show`, `/hide`,
+/// `/focus`, `/notify`. Returns the bound port so it can be passed to the
+/// sidecar via env.
+pub fn spawn(app_handle: AppHandle, token: String) -> std::io::Result<u16> {
+    let _ = APP_HANDLE.set(app_handle);
+
+    let listener = TcpListener::bind("127.0.0.1:0")?;
+    let port = listener.local_addr()?.port();
+
+    thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            handle_connection(stream, &token);
+        }
+    });
+
+    Ok(port)
+}
+
+fn handle_connection(mut stream: TcpStream, token: &str) {
+    let mut buf = [0u8; 1024];
+    let Ok(n) = stream.read(&mut buf) else {
+        return;
+    };
+    let request = String::from_utf8_lossy(&buf[..n]);
+
+    let mut lines = request.lines();
+    let Some(request_line) = lines.next() else {
+        return;
+    };
+    let mut parts = request_line.split_whitespace();
+    let (Some(_method), Some(path)) = (parts.next(), parts.next()) else {
+        return;
+    };
+
+    let authorized = lines.take_while(|line| !line.is_empty()).any(|line| {
+        line.split_once(':')
+            .is_some_and(|(name, value)| name.eq_ignore_ascii_case("x-agentos-token") && tokens_match(value.trim(), token))
+    });
+
+    if !authorized {
+        let _ = stream.write_all(b"HTTP/1.1 401 Unauthorized\r\nContent-Length: 0\r\n\r\n");
+        return;
+    }
+
+    let handled = match path.trim_start_matches('/') {
+        "show" => with_main_window(|window| {
+            let _ = window.show();
+            let _ = window.set_focus();
+        }),
+        "hide" => with_main_window(|window| {
+            let _ = window.hide();
+        }),
+        "focus" => with_main_window(|window| {
+            let _ = window.set_focus();
+        }),
+        "notify" => with_main_window(|window| {
+            let _ = window.emit("server-notify", ());
+        }),
+        _ => false,
+    };
+
+    let response = if handled {
+        "HTTP/1.1 200 OK\r\nContent-Length: 0\r\n\r\n"
+    } else {
+        "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\n\r\n"
+    };
+    let _ = stream.write_all(response.as_bytes());
+}
+
+fn with_main_window(action: impl FnOnce(&tauri::WebviewWindow)) -> bool {
+    let Some(window) = APP_HANDLE.get().and_then(|app| app.get_webview_window("main")) else {
+        return false;
+    };
+    action(&window);
+    true
+}
+
+#[tauri::command]
+pub fn show_window(window: tauri::WebviewWindow) -> Result<(), String> {
+    window.show().map_err(|e| e.to_string())?;
+    window.set_focus().map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn hide_window(window: tauri::WebviewWindow) -> Result<(), String> {
+    window.hide().map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn focus_window(window: tauri::WebviewWindow) -> Result<(), String> {
+    window.set_focus().map_err(|e| e.to_string())
+}
@@ -0,0 +1,161 @@
+//! Headless terminal dashboard, enabled by the `tui` feature and the
+//! `--ui`/`--tui` CLI flag. Monitors the supervised sidecar (PID, bound
+//! host/port, last successful health ping, restart count, recent output)
+//! without needing a GUI - useful for headless/server deployments.
+
+use std::io;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crossterm::event::{self, Event, KeyCode};
+use crossterm::terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen};
+use crossterm::execute;
+use ratatui::layout::{Constraint, Direction, Layout};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Borders, List, ListItem, Paragraph};
+use ratatui::{Frame, Terminal};
+use ratatui::backend::CrosstermBackend;
+
+use crate::config::{self, ServerConfig};
+use crate::server;
+use crate::status::Status;
+use crate::supervisor::{next_backoff, INITIAL_BACKOFF, MAX_RESTARTS};
+
+/// Returns true if the process was launched with `--ui` or `--tui`.
+pub fn requested() -> bool {
+    std::env::args().any(|arg| arg == "--ui" || arg == "--tui")
+}
+
+/// Runs the dashboard in place of the webview: starts the sidecar itself,
+/// supervises it with the same restart-with-backoff policy (and
+/// `MAX_RESTARTS` give-up cap) as `supervisor::spawn`, and renders its
+/// liveness until the user presses `q`/`Esc`.
+pub fn run() -> io::Result<()> {
+    let config = config::load(None);
+    let status = Arc::new(Status::default());
+    let mut child = server::start_and_wait(&config, None, &status);
+
+    enable_raw_mode()?;
+    let mut stdout = io::stdout();
+    execute!(stdout, EnterAlternateScreen)?;
+    let mut terminal = Terminal::new(CrosstermBackend::new(stdout))?;
+
+    let mut backoff = INITIAL_BACKOFF;
+    let mut restarts = 0u32;
+    let result = run_loop(&mut terminal, &config, &status, &mut child, &mut backoff, &mut restarts);
+
+    disable_raw_mode()?;
+    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+
+    if let Some(mut child) = child {
+        server::stop_gracefully(&mut child, config.shutdown_grace, &status);
+    }
+
+    result
+}
+
+/// Mirrors `supervisor::spawn`'s restart policy: exponential backoff capped
+/// at `MAX_BACKOFF`, giving up after `MAX_RESTARTS` attempts instead of
+/// retrying forever against a sidecar that can never start.
+fn run_loop(
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    config: &ServerConfig,
+    status: &Arc<Status>,
+    child: &mut Option<std::process::Child>,
+    backoff: &mut Duration,
+    restarts: &mut u32,
+) -> io::Result<()> {
+    let mut gave_up = false;
+
+    loop {
+        terminal.draw(|frame| draw(frame, config, status, gave_up))?;
+
+        if event::poll(Duration::from_millis(250))? {
+            if let Event::Key(key) = event::read()? {
+                if matches!(key.code, KeyCode::Char('q') | KeyCode::Esc) {
+                    return Ok(());
+                }
+            }
+        }
+
+        if gave_up {
+            continue;
+        }
+
+        let exited = match child.as_mut() {
+            Some(c) => matches!(c.try_wait(), Ok(Some(_))),
+            None => true,
+        };
+        if !exited {
+            continue;
+        }
+
+        if *restarts >= MAX_RESTARTS {
+            gave_up = true;
+            continue;
+        }
+
+        std::thread::sleep(*backoff);
+        *backoff = next_backoff(*backoff);
+        *restarts += 1;
+        status.record_restart();
+
+        *child = server::start_and_wait(config, None, status);
+        if child.is_some() {
+            *backoff = INITIAL_BACKOFF;
+        }
+    }
+}
+
+fn draw(frame: &mut Frame, config: &ServerConfig, status: &Status, gave_up: bool) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(6), Constraint::Min(0)])
+        .split(frame.area());
+
+    let pid = status
+        .pid
+        .lock()
+        .ok()
+        .and_then(|pid| *pid)
+        .map(|pid| pid.to_string())
+        .unwrap_or_else(|| "-".to_string());
+
+    let last_ping = status
+        .last_ping
+        .lock()
+        .ok()
+        .and_then(|ping| *ping)
+        .map(|at| format!("{:.1}s ago", at.elapsed().as_secs_f32()))
+        .unwrap_or_else(|| "never".to_string());
+
+    let restarts = status.restart_count.load(std::sync::atomic::Ordering::SeqCst);
+
+    let restart_line = if gave_up {
+        format!("restarts: {restarts} (gave up after {MAX_RESTARTS} attempts)")
+    } else {
+        format!("restarts: {restarts}")
+    };
+
+    let summary = Paragraph::new(vec![
+        Line::from(format!("server: {}:{}  pid: {pid}", config.host, config.port)),
+        Line::from(format!("last health ping: {last_ping}")),
+        Line::from(restart_line),
+        Line::from("press q to quit"),
+    ])
+    .block(Block::default().title("AgentOS status").borders(Borders::ALL));
+    frame.render_widget(summary, chunks[0]);
+
+    let tail_len = chunks[1].height.saturating_sub(2) as usize;
+    let lines: Vec<ListItem> = status
+        .log
+        .snapshot()
+        .iter()
+        .rev()
+        .take(tail_len)
+        .rev()
+        .map(|line| ListItem::new(line.clone()))
+        .collect();
+    let log = List::new(lines).block(Block::default().title("server output").borders(Borders::ALL));
+    frame.render_widget(log, chunks[1]);
+}
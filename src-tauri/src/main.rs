@@ -1,95 +1,121 @@
 // Prevents additional console window on Windows in release, DO NOT REMOVE!!
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
-use std::net::TcpStream;
-use std::process::{Child, Command};
-use std::sync::Mutex;
-use std::thread;
+use std::process::Child;
+use std::sync::atomic::Ordering;
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 use tauri::Manager;
 
-struct ServerProcess(Mutex<Option<Child>>);
+mod config;
+mod control;
+#[cfg(feature = "tui")]
+mod dashboard;
+mod server;
+mod status;
+mod supervisor;
+mod tray;
 
-fn start_server() -> Option<Child> {
-    let current_dir = std::env::current_dir().ok()?;
+use status::Status;
+use supervisor::ShuttingDown;
 
-    // If we're in src-tauri, go up to the project root
-    let project_root = if current_dir.ends_with("src-tauri") {
-        current_dir.parent()?.to_path_buf()
-    } else {
-        current_dir
-    };
+pub(crate) struct ServerProcess(pub(crate) Mutex<Option<Child>>);
 
-    let server_path = project_root.join("dist/server.js");
+/// Grace period handed to `server::stop_gracefully`, stashed in app state so
+/// both the tray's Quit item and the `RunEvent::Exit` handler can reach it.
+pub(crate) struct ShutdownGrace(pub(crate) Duration);
 
-    // Check if we're in production (dist/server.js) or development
-    let (cmd, args, working_dir) = if server_path.exists() {
-        ("node", vec!["dist/server.js"], project_root)
-    } else {
-        // Development mode - run with tsx
-        ("npx", vec!["tsx", "server.ts"], project_root)
-    };
+/// Marks the shutdown as intentional (so the supervisor doesn't race it
+/// with a restart) and gracefully stops the managed sidecar. Shared by the
+/// tray's Quit item and the app's own exit path so the server is reaped
+/// however the process ends.
+pub(crate) fn graceful_shutdown(app: &tauri::AppHandle) {
+    app.state::<ShuttingDown>().0.store(true, Ordering::SeqCst);
 
-    println!("Starting AgentOS server...");
-    println!("Working dir: {:?}", working_dir);
+    let grace = app.state::<ShutdownGrace>().0;
+    let status = app.state::<Arc<Status>>().inner().clone();
 
-    let child = Command::new(cmd)
-        .args(&args)
-        .current_dir(&working_dir)
-        .spawn()
-        .ok()?;
+    // Take the child and drop the lock before the (potentially
+    // multi-second) graceful stop, so the supervisor thread's own lock
+    // attempts aren't blocked for the whole grace period.
+    let child = app
+        .state::<ServerProcess>()
+        .0
+        .lock()
+        .ok()
+        .and_then(|mut guard| guard.take());
 
-    println!("Server started with PID: {}", child.id());
-    Some(child)
-}
-
-fn wait_for_server(host: &str, port: u16, max_attempts: u32) -> bool {
-    for attempt in 1..=max_attempts {
-        if TcpStream::connect((host, port)).is_ok() {
-            println!("Server ready after {} attempts", attempt);
-            return true;
-        }
-        println!("Waiting for server... attempt {}/{}", attempt, max_attempts);
-        thread::sleep(Duration::from_millis(500));
+    if let Some(mut child) = child {
+        server::stop_gracefully(&mut child, grace, &status);
     }
-    false
 }
 
 fn main() {
-    tauri::Builder::default()
+    // `--ui`/`--tui` launches a headless ratatui dashboard instead of the
+    // webview, for server deployments that want liveness without a GUI.
+    #[cfg(feature = "tui")]
+    if dashboard::requested() {
+        dashboard::run().expect("dashboard failed");
+        return;
+    }
+
+    let app = tauri::Builder::default()
         .plugin(tauri_plugin_shell::init())
+        .invoke_handler(tauri::generate_handler![
+            control::show_window,
+            control::hide_window,
+            control::focus_window,
+        ])
         .setup(|app| {
-            // Start the Node.js server
-            let server = start_server();
-
-            if server.is_some() {
-                // Wait for server to be ready (max 30 seconds)
-                let ready = wait_for_server("127.0.0.1", 3011, 60);
-                if !ready {
-                    eprintln!("Warning: Server may not be ready");
-                }
-            } else {
-                eprintln!("Warning: Could not start server - assuming it's already running");
-            }
+            // Resolve host/port/command/timeout from config.toml (app
+            // config dir) + AGENTOS_* env overrides.
+            let config_dir = app.path().app_config_dir().ok();
+            let config = config::load(config_dir);
+
+            // Bind the loopback control channel before starting the
+            // sidecar so its port/token can be handed to the server on
+            // spawn.
+            let control_token = control::generate_token();
+            let control_port = control::spawn(app.handle().clone(), control_token.clone())
+                .expect("failed to bind control channel");
+
+            let status = Arc::new(Status::default());
+            let server = server::start_and_wait(&config, Some((control_port, &control_token)), &status);
 
             // Store the server process handle for cleanup
             app.manage(ServerProcess(Mutex::new(server)));
+            app.manage(ShuttingDown::default());
+            app.manage(ShutdownGrace(config.shutdown_grace));
+            app.manage(status.clone());
+
+            // Watch the sidecar and restart it with backoff if it dies
+            // mid-session, unless we're in the middle of an intentional
+            // shutdown.
+            supervisor::spawn(app.handle().clone(), config, control_port, control_token, status);
+
+            tray::setup(app.handle())?;
 
             Ok(())
         })
         .on_window_event(|window, event| {
-            if let tauri::WindowEvent::CloseRequested { .. } = event {
-                // Kill the server when window closes
-                if let Some(state) = window.try_state::<ServerProcess>() {
-                    if let Ok(mut guard) = state.0.lock() {
-                        if let Some(mut child) = guard.take() {
-                            println!("Stopping server...");
-                            let _ = child.kill();
-                        }
-                    }
-                }
+            if let tauri::WindowEvent::CloseRequested { api, .. } = event {
+                // Minimize to tray instead of tearing down the server: any
+                // in-flight agent work keeps running in the background.
+                // The server is only stopped via the tray's Quit item or
+                // when the app exits (see `RunEvent::Exit` below).
+                api.prevent_close();
+                let _ = window.hide();
             }
         })
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application");
+
+    app.run(|app_handle, event| {
+        // Reached on unexpected app termination paths too, so the sidecar
+        // is reliably reaped even when the user never goes through the
+        // tray's Quit item.
+        if let tauri::RunEvent::Exit = event {
+            graceful_shutdown(app_handle);
+        }
+    });
 }
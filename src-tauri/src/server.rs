@@ -0,0 +1,248 @@
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpStream;
+use std::process::{Child, Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::config::ServerConfig;
+use crate::status::Status;
+
+/// Path queried on every readiness attempt. The Node server is expected to
+/// answer with a `2xx` once it's actually able to serve requests.
+const HEALTH_PATH: &str = "/health";
+
+fn start_server(config: &ServerConfig, control: Option<(u16, &str)>, status: &Status) -> Option<Child> {
+    let current_dir = std::env::current_dir().ok()?;
+
+    // If we're in src-tauri, go up to the project root
+    let project_root = if current_dir.ends_with("src-tauri") {
+        current_dir.parent()?.to_path_buf()
+    } else {
+        current_dir
+    };
+    // An explicit `working_dir` also applies to the auto-detected command.
+    let project_root = config.working_dir.clone().unwrap_or(project_root);
+
+    let (cmd, args, working_dir) = if let Some(command) = &config.command {
+        (
+            command.clone(),
+            config.args.clone(),
+            config.working_dir.clone().unwrap_or(project_root),
+        )
+    } else {
+        let server_path = project_root.join("dist/server.js");
+        // Check if we're in production (dist/server.js) or development
+        if server_path.exists() {
+            ("node".to_string(), vec!["dist/server.js".to_string()], project_root)
+        } else {
+            // Development mode - run with tsx
+            ("npx".to_string(), vec!["tsx".to_string(), "server.ts".to_string()], project_root)
+        }
+    };
+
+    println!("Starting AgentOS server...");
+    println!("Working dir: {:?}", working_dir);
+
+    let mut command = Command::new(&cmd);
+    command
+        .args(&args)
+        .current_dir(&working_dir)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+
+    // Let the server call back into the desktop shell over the loopback
+    // control channel (see control.rs).
+    if let Some((control_port, control_token)) = control {
+        command
+            .env("AGENTOS_CONTROL_PORT", control_port.to_string())
+            .env("AGENTOS_CONTROL_TOKEN", control_token);
+    }
+
+    let mut child = command.spawn().ok()?;
+
+    println!("Server started with PID: {}", child.id());
+
+    // Pipe stdout/stderr into the bounded ring buffer the `--ui` dashboard
+    // tails, instead of leaving them inherited.
+    if let Some(stdout) = child.stdout.take() {
+        spawn_log_reader(stdout, status.log_handle());
+    }
+    if let Some(stderr) = child.stderr.take() {
+        spawn_log_reader(stderr, status.log_handle());
+    }
+
+    Some(child)
+}
+
+fn spawn_log_reader(pipe: impl Read + Send + 'static, log: std::sync::Arc<crate::status::LogBuffer>) {
+    thread::spawn(move || {
+        for line in BufReader::new(pipe).lines().map_while(Result::ok) {
+            log.push(line);
+        }
+    });
+}
+
+/// Result of waiting for the sidecar to become ready.
+enum ReadinessOutcome {
+    Ready,
+    TimedOut,
+    ServerExited(std::process::ExitStatus),
+}
+
+/// Issues a minimal HTTP/1.1 GET against `host:port` + `path` and reports
+/// whether the response line was a `2xx`. Written by hand instead of pulling
+/// in an HTTP client crate, since a readiness probe only ever needs the
+/// status line.
+///
+/// Reads only the status line (not the full response) so a server that
+/// doesn't honor `Connection: close` and keeps the body/connection open
+/// can't stretch one probe attempt out to the full read timeout on top of
+/// the inter-attempt sleep.
+fn http_health_check(host: &str, port: u16, path: &str) -> bool {
+    let mut stream = match TcpStream::connect((host, port)) {
+        Ok(stream) => stream,
+        Err(_) => return false,
+    };
+    if stream.set_read_timeout(Some(Duration::from_millis(500))).is_err() {
+        return false;
+    }
+
+    let request = format!(
+        "GET {path} HTTP/1.1\r\nHost: {host}:{port}\r\nConnection: close\r\n\r\n"
+    );
+    if stream.write_all(request.as_bytes()).is_err() {
+        return false;
+    }
+
+    let mut status_line = String::new();
+    if BufReader::new(stream).read_line(&mut status_line).is_err() {
+        return false;
+    }
+
+    parse_status_code(&status_line).is_some_and(|code| (200..300).contains(&code))
+}
+
+/// Extracts the numeric status code from an HTTP response's first line,
+/// e.g. `"HTTP/1.1 200 OK\r\n"` -> `Some(200)`.
+fn parse_status_code(status_line: &str) -> Option<u16> {
+    status_line.split_whitespace().nth(1)?.parse().ok()
+}
+
+fn wait_for_server(host: &str, port: u16, path: &str, child: &mut Child, max_attempts: u32) -> ReadinessOutcome {
+    for attempt in 1..=max_attempts {
+        if let Ok(Some(status)) = child.try_wait() {
+            println!("Server exited early with {status} before becoming ready");
+            return ReadinessOutcome::ServerExited(status);
+        }
+
+        if http_health_check(host, port, path) {
+            println!("Server ready after {} attempts", attempt);
+            return ReadinessOutcome::Ready;
+        }
+
+        println!("Waiting for server... attempt {}/{}", attempt, max_attempts);
+        thread::sleep(Duration::from_millis(500));
+    }
+    ReadinessOutcome::TimedOut
+}
+
+/// Starts the sidecar and blocks until it reports healthy, exits early, or
+/// we run out of attempts. Set `AGENTOS_SKIP_SERVER_CHECK=true` to start the
+/// process (or assume an externally managed one) without probing it at all.
+pub fn start_and_wait(config: &ServerConfig, control: Option<(u16, &str)>, status: &Status) -> Option<Child> {
+    let server = start_server(config, control, status);
+
+    let skip_check = std::env::var("AGENTOS_SKIP_SERVER_CHECK")
+        .map(|value| value.eq_ignore_ascii_case("true"))
+        .unwrap_or(false);
+
+    let Some(mut child) = server else {
+        eprintln!("Warning: Could not start server - assuming it's already running");
+        return None;
+    };
+    status.mark_spawned(child.id());
+
+    if skip_check {
+        println!("AGENTOS_SKIP_SERVER_CHECK set - skipping readiness probe");
+        return Some(child);
+    }
+
+    match wait_for_server(&config.host, config.port, HEALTH_PATH, &mut child, config.max_attempts()) {
+        ReadinessOutcome::Ready => {
+            status.mark_healthy();
+            Some(child)
+        }
+        ReadinessOutcome::TimedOut => {
+            eprintln!("Warning: Server may not be ready");
+            Some(child)
+        }
+        ReadinessOutcome::ServerExited(exit_status) => {
+            eprintln!("Error: server exited before becoming ready ({exit_status})");
+            status.mark_stopped();
+            None
+        }
+    }
+}
+
+/// Asks the sidecar to exit cleanly (SIGTERM on Unix, `taskkill` without
+/// `/F` on Windows) and gives it `grace` to do so before escalating to a
+/// hard `kill()`. Shells out to the platform's own signal/termination tool
+/// rather than adding a process-control crate for a single call.
+pub fn stop_gracefully(child: &mut Child, grace: Duration, status: &Status) {
+    if matches!(child.try_wait(), Ok(Some(_))) {
+        status.mark_stopped();
+        return;
+    }
+
+    println!("Stopping server (PID {})...", child.id());
+    send_terminate(child.id());
+
+    let deadline = Instant::now() + grace;
+    while Instant::now() < deadline {
+        if matches!(child.try_wait(), Ok(Some(_))) {
+            status.mark_stopped();
+            return;
+        }
+        thread::sleep(Duration::from_millis(100));
+    }
+
+    eprintln!("Server did not exit within {grace:?}, killing");
+    let _ = child.kill();
+    let _ = child.wait();
+    status.mark_stopped();
+}
+
+#[cfg(unix)]
+fn send_terminate(pid: u32) {
+    let _ = Command::new("kill").arg("-TERM").arg(pid.to_string()).status();
+}
+
+#[cfg(windows)]
+fn send_terminate(pid: u32) {
+    let _ = Command::new("taskkill")
+        .args(["/PID", &pid.to_string()])
+        .status();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_status_code_reads_2xx() {
+        assert_eq!(parse_status_code("HTTP/1.1 200 OK\r\n"), Some(200));
+        assert_eq!(parse_status_code("HTTP/1.1 204 No Content\r\n"), Some(204));
+    }
+
+    #[test]
+    fn parse_status_code_reads_non_2xx() {
+        assert_eq!(parse_status_code("HTTP/1.1 503 Service Unavailable\r\n"), Some(503));
+        assert_eq!(parse_status_code("HTTP/1.1 404 Not Found\r\n"), Some(404));
+    }
+
+    #[test]
+    fn parse_status_code_rejects_malformed_line() {
+        assert_eq!(parse_status_code(""), None);
+        assert_eq!(parse_status_code("garbage"), None);
+    }
+}
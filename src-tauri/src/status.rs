@@ -0,0 +1,75 @@
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+/// Max number of stdout/stderr lines kept from the sidecar. Old lines are
+/// dropped once the tail grows past this so memory stays bounded for
+/// long-running sessions.
+const LOG_CAPACITY: usize = 200;
+
+/// Bounded tail of the sidecar's stdout/stderr, fed by the reader threads
+/// spawned in `server::start_server` and rendered by the optional `--ui`
+/// dashboard (see `dashboard.rs`).
+#[derive(Default)]
+pub struct LogBuffer(Mutex<VecDeque<String>>);
+
+impl LogBuffer {
+    pub fn push(&self, line: String) {
+        if let Ok(mut buf) = self.0.lock() {
+            if buf.len() >= LOG_CAPACITY {
+                buf.pop_front();
+            }
+            buf.push_back(line);
+        }
+    }
+
+    pub fn snapshot(&self) -> Vec<String> {
+        self.0
+            .lock()
+            .map(|buf| buf.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+}
+
+/// Live liveness info for the supervised sidecar: PID, last successful
+/// health ping, restart count, and its recent output. Shared between
+/// `server`/`supervisor` (writers) and the optional `--ui` dashboard
+/// (reader).
+#[derive(Default)]
+pub struct Status {
+    pub pid: Mutex<Option<u32>>,
+    pub last_ping: Mutex<Option<Instant>>,
+    pub restart_count: AtomicU32,
+    pub log: Arc<LogBuffer>,
+}
+
+impl Status {
+    /// Cheap handle to the log buffer for reader threads that outlive the
+    /// call into `server::start_server`.
+    pub fn log_handle(&self) -> Arc<LogBuffer> {
+        self.log.clone()
+    }
+
+    /// Records that a sidecar process is running, independent of whether
+    /// it has ever answered the health check. Lets the dashboard show a
+    /// PID before (or without) confirmation that the server is healthy.
+    pub fn mark_spawned(&self, pid: u32) {
+        *self.pid.lock().unwrap() = Some(pid);
+    }
+
+    /// Records a confirmed `2xx` health check. Only this should update
+    /// `last_ping` - it's surfaced to the dashboard as "last successful
+    /// health ping" and must not be set on a timeout or a skipped probe.
+    pub fn mark_healthy(&self) {
+        *self.last_ping.lock().unwrap() = Some(Instant::now());
+    }
+
+    pub fn mark_stopped(&self) {
+        *self.pid.lock().unwrap() = None;
+    }
+
+    pub fn record_restart(&self) -> u32 {
+        self.restart_count.fetch_add(1, Ordering::SeqCst) + 1
+    }
+}
@@ -0,0 +1,133 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::config::ServerConfig;
+use crate::server;
+use crate::status::Status;
+use crate::ServerProcess;
+
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Restart backoff policy, shared with the headless `--ui` dashboard
+/// (`dashboard.rs`) so both supervision loops give up after the same number
+/// of attempts instead of the dashboard running its own, looser copy.
+pub(crate) const INITIAL_BACKOFF: Duration = Duration::from_millis(500);
+pub(crate) const MAX_BACKOFF: Duration = Duration::from_secs(30);
+pub(crate) const MAX_RESTARTS: u32 = 10;
+
+/// Set by `graceful_shutdown` (tray Quit and app exit) before stopping the
+/// sidecar so the
+/// supervisor doesn't race the kill path and respawn a server we're trying
+/// to stop.
+#[derive(Default)]
+pub struct ShuttingDown(pub AtomicBool);
+
+/// Spawns a background thread that periodically checks whether the managed
+/// sidecar is still alive and, if it exited unexpectedly, restarts it with
+/// exponential backoff (capped at `MAX_RESTARTS` attempts). Emits
+/// `server-restarted` / `server-failed` events the frontend can subscribe
+/// to, and replaces the `Child` handle stored in `ServerProcess` in place.
+pub fn spawn(
+    app_handle: AppHandle,
+    config: ServerConfig,
+    control_port: u16,
+    control_token: String,
+    status: Arc<Status>,
+) {
+    thread::spawn(move || {
+        let mut backoff = INITIAL_BACKOFF;
+        let mut restarts = 0u32;
+        // Set when the last restart attempt failed, so the next poll retries
+        // (with the grown backoff) even though no child is stored.
+        let mut retry_pending = false;
+
+        loop {
+            thread::sleep(POLL_INTERVAL);
+
+            if app_handle.state::<ShuttingDown>().0.load(Ordering::SeqCst) {
+                break;
+            }
+
+            // Only an actually-observed `try_wait()` exit counts as "died
+            // unexpectedly". A missing child (`None`) just means nothing is
+            // being supervised right now - e.g. the shutdown path just
+            // `take()`n it - and must never be treated the same as a crash.
+            let exited = retry_pending || {
+                let state = app_handle.state::<ServerProcess>();
+                match state.0.lock() {
+                    Ok(mut guard) => match guard.as_mut() {
+                        Some(child) => matches!(child.try_wait(), Ok(Some(_))),
+                        None => false,
+                    },
+                    Err(_) => continue,
+                }
+            };
+
+            if !exited {
+                continue;
+            }
+
+            if restarts >= MAX_RESTARTS {
+                eprintln!("Server supervisor: giving up after {restarts} restarts");
+                let _ = app_handle.emit("server-failed", restarts);
+                break;
+            }
+
+            eprintln!("Server supervisor: server exited unexpectedly, restarting in {backoff:?}");
+            thread::sleep(backoff);
+            backoff = next_backoff(backoff);
+
+            // Re-check right before respawning: the sleep above gives a
+            // concurrent shutdown plenty of time to land, and we must not
+            // spawn a new, unmanaged sidecar after (or during) app exit.
+            if app_handle.state::<ShuttingDown>().0.load(Ordering::SeqCst) {
+                break;
+            }
+
+            restarts += 1;
+            status.record_restart();
+
+            let restarted = server::start_and_wait(&config, Some((control_port, &control_token)), &status);
+            let restarted_ok = restarted.is_some();
+            retry_pending = !restarted_ok;
+            if let Ok(mut guard) = app_handle.state::<ServerProcess>().0.lock() {
+                *guard = restarted;
+            }
+
+            if restarted_ok {
+                backoff = INITIAL_BACKOFF;
+                let _ = app_handle.emit("server-restarted", restarts);
+            } else {
+                let _ = app_handle.emit("server-failed", restarts);
+            }
+        }
+    });
+}
+
+/// Doubles the backoff, capped at `MAX_BACKOFF`. Pulled out as a pure
+/// function so the progression can be unit tested without spinning up a
+/// thread.
+pub(crate) fn next_backoff(current: Duration) -> Duration {
+    (current * 2).min(MAX_BACKOFF)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn next_backoff_doubles() {
+        assert_eq!(next_backoff(INITIAL_BACKOFF), Duration::from_secs(1));
+        assert_eq!(next_backoff(Duration::from_secs(1)), Duration::from_secs(2));
+    }
+
+    #[test]
+    fn next_backoff_caps_at_max() {
+        assert_eq!(next_backoff(Duration::from_secs(20)), MAX_BACKOFF);
+        assert_eq!(next_backoff(MAX_BACKOFF), MAX_BACKOFF);
+    }
+}
@@ -0,0 +1,44 @@
+use tauri::menu::{Menu, MenuItem};
+use tauri::tray::TrayIconBuilder;
+use tauri::{AppHandle, Manager};
+
+/// Builds the tray icon (Show / Hide / Quit) used to keep the app running
+/// in the background when the window is closed. Only the Quit item
+/// actually tears down the managed sidecar.
+pub fn setup(app: &AppHandle) -> tauri::Result<()> {
+    let show_item = MenuItem::with_id(app, "show", "Show", true, None::<&str>)?;
+    let hide_item = MenuItem::with_id(app, "hide", "Hide", true, None::<&str>)?;
+    let quit_item = MenuItem::with_id(app, "quit", "Quit", true, None::<&str>)?;
+    let menu = Menu::with_items(app, &[&show_item, &hide_item, &quit_item])?;
+
+    TrayIconBuilder::new()
+        .icon(app.default_window_icon().cloned().unwrap_or_default())
+        .menu(&menu)
+        .on_menu_event(|app, event| match event.id.as_ref() {
+            "show" => {
+                if let Some(window) = app.get_webview_window("main") {
+                    let _ = window.show();
+                    let _ = window.set_focus();
+                }
+            }
+            "hide" => {
+                if let Some(window) = app.get_webview_window("main") {
+                    let _ = window.hide();
+                }
+            }
+            "quit" => quit(app),
+            _ => {}
+        })
+        .build(app)?;
+
+    Ok(())
+}
+
+/// Gracefully stops the managed sidecar and exits the app. This is the
+/// only path (besides unexpected app termination, see `RunEvent::Exit` in
+/// `main.rs`) that tears down the server - closing the window just hides
+/// it (see the `CloseRequested` handler in `main.rs`).
+pub fn quit(app: &AppHandle) {
+    crate::graceful_shutdown(app);
+    app.exit(0);
+}